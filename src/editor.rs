@@ -0,0 +1,117 @@
+use crate::Graph;
+
+/// A reversible graph edit. `undo` is handed the graph *after* `apply` ran
+/// and returns the inverse command, so undoing is just applying it.
+pub trait Command {
+    fn apply(&self, graph: &mut Graph);
+    fn undo(&self, graph: &Graph) -> Box<dyn Command>;
+}
+
+pub struct ToggleEdge {
+    pub i: usize,
+    pub j: usize,
+}
+
+impl Command for ToggleEdge {
+    fn apply(&self, graph: &mut Graph) {
+        let value = !graph.edges[self.i][self.j];
+        graph.manage_edge(self.i, self.j, value);
+    }
+
+    fn undo(&self, _graph: &Graph) -> Box<dyn Command> {
+        Box::new(ToggleEdge {
+            i: self.i,
+            j: self.j,
+        })
+    }
+}
+
+pub struct AddNode;
+
+impl Command for AddNode {
+    fn apply(&self, graph: &mut Graph) {
+        graph.add_node();
+    }
+
+    fn undo(&self, graph: &Graph) -> Box<dyn Command> {
+        Box::new(DeleteNode::new(graph, graph.nodes_n - 1))
+    }
+}
+
+pub struct DeleteNode {
+    index: usize,
+    /// The removed node's edges to every node that survives the deletion,
+    /// i.e. its row with the (always-false) self entry trimmed out.
+    edges: Vec<bool>,
+}
+
+impl DeleteNode {
+    pub fn new(graph: &Graph, index: usize) -> Self {
+        let mut edges = graph.edges[index].clone();
+        edges.remove(index);
+
+        Self { index, edges }
+    }
+}
+
+impl Command for DeleteNode {
+    fn apply(&self, graph: &mut Graph) {
+        graph.remove_node(self.index);
+    }
+
+    fn undo(&self, _graph: &Graph) -> Box<dyn Command> {
+        Box::new(InsertNode {
+            index: self.index,
+            edges: self.edges.clone(),
+        })
+    }
+}
+
+/// Inverse of `DeleteNode`: reinserts a node at its original position with
+/// its original edges.
+struct InsertNode {
+    index: usize,
+    edges: Vec<bool>,
+}
+
+impl Command for InsertNode {
+    fn apply(&self, graph: &mut Graph) {
+        graph.insert_node(self.index, self.edges.clone());
+    }
+
+    fn undo(&self, _graph: &Graph) -> Box<dyn Command> {
+        Box::new(DeleteNode {
+            index: self.index,
+            edges: self.edges.clone(),
+        })
+    }
+}
+
+/// Undo/redo stacks wired to Ctrl+Z / Ctrl+Shift+Z.
+#[derive(Default)]
+pub struct History {
+    undo_stack: Vec<Box<dyn Command>>,
+    redo_stack: Vec<Box<dyn Command>>,
+}
+
+impl History {
+    pub fn apply(&mut self, graph: &mut Graph, command: Box<dyn Command>) {
+        command.apply(graph);
+        self.undo_stack.push(command);
+        self.redo_stack.clear();
+    }
+
+    pub fn undo(&mut self, graph: &mut Graph) {
+        if let Some(command) = self.undo_stack.pop() {
+            command.undo(graph).apply(graph);
+            self.redo_stack.push(command);
+        }
+    }
+
+    pub fn redo(&mut self, graph: &mut Graph) {
+        if let Some(command) = self.redo_stack.pop() {
+            command.apply(graph);
+            self.undo_stack.push(command);
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use crate::Graph;
+use std::io;
+
+/// Writes `graph` out as Graphviz DOT: `graph { ... }` with a bare node
+/// statement per vertex (so isolated nodes round-trip) and one edge line per
+/// set upper-triangular entry.
+pub fn write_dot(graph: &Graph, path: &str) -> io::Result<()> {
+    let mut out = String::from("graph {\n");
+
+    for i in 0..graph.nodes_n {
+        out.push_str(&format!("    {i};\n"));
+    }
+
+    for i in 0..graph.nodes_n {
+        for j in i + 1..graph.nodes_n {
+            if graph.edges[i][j] {
+                out.push_str(&format!("    {i} -- {j};\n"));
+            }
+        }
+    }
+
+    out.push_str("}\n");
+
+    std::fs::write(path, out)
+}
+
+/// Reads a DOT file written by [`write_dot`] back into a [`Graph`]. `nodes_n`
+/// comes from the highest node id seen across both the bare node statements
+/// and the edge statements, not just the edges -- an isolated node has no
+/// edge to derive it from.
+pub fn read_dot(path: &str) -> io::Result<Graph> {
+    let contents = std::fs::read_to_string(path)?;
+
+    let mut nodes_n = 0;
+    let mut edges = Vec::new();
+
+    for line in contents.lines() {
+        let line = line.trim().trim_end_matches(';');
+
+        if let Some((a, b)) = line.split_once("--") {
+            let Some((a, b)) = a.trim().parse().ok().zip(b.trim().parse().ok()) else {
+                continue;
+            };
+
+            nodes_n = nodes_n.max(a + 1).max(b + 1);
+            edges.push((a, b));
+        } else if let Ok(node) = line.parse::<usize>() {
+            nodes_n = nodes_n.max(node + 1);
+        }
+    }
+
+    let mut graph = Graph::new(nodes_n);
+
+    for (i, j) in edges {
+        graph.manage_edge(i, j, true);
+    }
+
+    Ok(graph)
+}
+
+/// Writes `graph` out as GraphML: a `<node>` per vertex and an `<edge>` per
+/// set upper-triangular entry.
+pub fn write_graphml(graph: &Graph, path: &str) -> io::Result<()> {
+    let mut out = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n\
+         \x20\x20<graph edgedefault=\"undirected\">\n",
+    );
+
+    for i in 0..graph.nodes_n {
+        out.push_str(&format!("    <node id=\"n{i}\"/>\n"));
+    }
+
+    let mut edge_id = 0;
+
+    for i in 0..graph.nodes_n {
+        for j in i + 1..graph.nodes_n {
+            if graph.edges[i][j] {
+                out.push_str(&format!(
+                    "    <edge id=\"e{edge_id}\" source=\"n{i}\" target=\"n{j}\"/>\n"
+                ));
+                edge_id += 1;
+            }
+        }
+    }
+
+    out.push_str("  </graph>\n</graphml>\n");
+
+    std::fs::write(path, out)
+}
+
+/// Reads a GraphML file written by [`write_graphml`] back into a [`Graph`],
+/// collecting node ids and edge endpoints into `manage_edge` calls.
+pub fn read_graphml(path: &str) -> io::Result<Graph> {
+    let contents = std::fs::read_to_string(path)?;
+
+    let mut node_ids = Vec::new();
+    let mut edge_pairs = Vec::new();
+
+    for line in contents.lines() {
+        let line = line.trim();
+
+        if let Some(id) = line.strip_prefix("<node ").and_then(|_| attr(line, "id")) {
+            node_ids.push(id);
+        } else if line.starts_with("<edge ") {
+            if let (Some(source), Some(target)) = (attr(line, "source"), attr(line, "target")) {
+                edge_pairs.push((source, target));
+            }
+        }
+    }
+
+    let mut graph = Graph::new(node_ids.len());
+
+    for (source, target) in edge_pairs {
+        let i = node_ids.iter().position(|id| *id == source).unwrap_or(0);
+        let j = node_ids.iter().position(|id| *id == target).unwrap_or(0);
+
+        graph.manage_edge(i, j, true);
+    }
+
+    Ok(graph)
+}
+
+fn attr(line: &str, name: &str) -> Option<String> {
+    let needle = format!("{name}=\"");
+    let start = line.find(&needle)? + needle.len();
+    let end = start + line[start..].find('"')?;
+
+    Some(line[start..end].to_string())
+}
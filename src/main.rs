@@ -1,17 +1,25 @@
-use ::rand::random;
+mod editor;
+mod io;
+mod settings;
+mod train;
+
+use ::rand::{rngs::StdRng, Rng, SeedableRng};
 use macroquad::prelude::*;
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
+use settings::{Format, Mode, Settings};
 use std::{f32::consts::PI, process::exit, time::Instant};
 
-const NODES_N: usize = 11;
-static MAX_EDGES_N: usize = (NODES_N * (NODES_N - 1)) / 2;
-static EDGES_THRESHOLD: usize = (MAX_EDGES_N as f32 * 0.5) as usize;
-
-const GRAPHS_N: usize = 100000;
-
 const GRAPH_RADIUS: f32 = 500.0;
 const NODE_RADIUS: f32 = 10.0;
 
+const LAYOUT_ITERATIONS: usize = 100;
+
+/// Held-Karp is `O(2^n * n^2)`; the bitset dp table costs `n * 2^n` bits
+/// (~48 MiB at 24 nodes, see `Graph::get_cycle`), so the binding constraint
+/// at this cap is the DP's runtime, not its memory.
+const MAX_NODES_N: usize = 24;
+
 #[derive(Serialize, Deserialize)]
 struct Graph {
     nodes_n: usize,
@@ -22,6 +30,7 @@ struct Graph {
 struct Info {
     nodes_n: usize,
     graphs_n: usize,
+    seed: u64,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -41,33 +50,80 @@ impl GraphKind {
 }
 
 impl Graph {
-    fn get_path(&self, mut path: Vec<usize>, current_node: usize) -> Vec<usize> {
-        if current_node == 0 && path.len() == NODES_N {
-            path.push(0);
-            return path;
-        }
+    /// Held-Karp bitmask DP: `dp[v]` holds, as a bitset over `mask`, whether
+    /// there is a simple path starting at node 0, visiting exactly the vertex
+    /// set `mask` (which always includes bit 0), and currently ending at `v`.
+    /// Runs in `O(2^n * n^2)`, which is what lets the node count scale well
+    /// past what the old backtracking DFS could handle. The table packs one
+    /// bit per `(mask, v)` pair rather than a `bool`/`usize` per entry, and
+    /// predecessors are re-derived during backtracking instead of stored --
+    /// a stored predecessor table would otherwise dwarf the dp table itself.
+    fn get_cycle(&self) -> Vec<usize> {
+        let n = self.nodes_n;
 
-        if path.contains(&current_node) {
+        if n < 3 {
             return vec![];
         }
 
-        path.push(current_node);
+        let full: usize = (1 << n) - 1;
+        let words = (1usize << n).div_ceil(64);
+
+        let mut dp = vec![vec![0u64; words]; n];
+
+        let get =
+            |dp: &[Vec<u64>], v: usize, mask: usize| dp[v][mask / 64] & (1 << (mask % 64)) != 0;
+        let set = |dp: &mut [Vec<u64>], v: usize, mask: usize| dp[v][mask / 64] |= 1 << (mask % 64);
+
+        set(&mut dp, 0, 1);
+
+        for mask in 1..=full {
+            if mask & 1 == 0 {
+                continue;
+            }
+
+            for v in 0..n {
+                if !get(&dp, v, mask) {
+                    continue;
+                }
 
-        for node in 0..NODES_N {
-            if self.edges[current_node][node] {
-                let path = self.get_path(path.clone(), node);
+                for u in 0..n {
+                    if mask & (1 << u) != 0 || !self.edges[v][u] {
+                        continue;
+                    }
 
-                if !path.is_empty() {
-                    return path;
+                    set(&mut dp, u, mask | (1 << u));
                 }
             }
         }
 
-        vec![]
-    }
+        let Some(last) = (0..n).find(|&v| get(&dp, v, full) && self.edges[v][0]) else {
+            return vec![];
+        };
 
-    fn get_cycle(&self) -> Vec<usize> {
-        self.get_path(Vec::with_capacity(NODES_N), 0)
+        let mut cycle = Vec::with_capacity(n + 1);
+        let mut mask = full;
+        let mut node = last;
+
+        loop {
+            cycle.push(node);
+
+            if mask == 1 {
+                break;
+            }
+
+            let prev_mask = mask & !(1 << node);
+            let prev = (0..n)
+                .find(|&u| get(&dp, u, prev_mask) && self.edges[u][node])
+                .unwrap();
+
+            mask = prev_mask;
+            node = prev;
+        }
+
+        cycle.reverse();
+        cycle.push(0);
+
+        cycle
     }
 
     fn safety(&self) {
@@ -76,6 +132,11 @@ impl Graph {
             eprintln!("The number of nodes can't be lower than 3.");
             exit(1);
         }
+
+        if self.nodes_n > MAX_NODES_N {
+            eprintln!("The number of nodes can't be higher than {MAX_NODES_N}.");
+            exit(1);
+        }
     }
 
     fn new(nodes_n: usize) -> Self {
@@ -89,17 +150,115 @@ impl Graph {
         self.edges[j][i] = value;
     }
 
-    fn generate(&mut self) {
+    fn add_node(&mut self) {
+        for row in &mut self.edges {
+            row.push(false);
+        }
+
+        self.edges.push(vec![false; self.nodes_n + 1]);
+        self.nodes_n += 1;
+    }
+
+    fn remove_node(&mut self, index: usize) {
+        self.edges.remove(index);
+
+        for row in &mut self.edges {
+            row.remove(index);
+        }
+
+        self.nodes_n -= 1;
+    }
+
+    /// Inverse of `remove_node`: reinserts a node at `index` with `edges`
+    /// (one entry per *current* node, i.e. `self.nodes_n` of them) describing
+    /// which of them it's connected to.
+    fn insert_node(&mut self, index: usize, edges: Vec<bool>) {
+        for (i, row) in self.edges.iter_mut().enumerate() {
+            row.insert(index, edges[i]);
+        }
+
+        let mut row = edges;
+        row.insert(index, false);
+        self.edges.insert(index, row);
+
+        self.nodes_n += 1;
+    }
+
+    fn generate(&mut self, rng: &mut StdRng) {
         for i in 0..self.nodes_n {
             for j in i + 1..self.nodes_n {
-                self.manage_edge(i, j, random::<bool>());
+                self.manage_edge(i, j, rng.gen::<bool>());
             }
         }
     }
 
-    fn generate_with_given_kind(&mut self, kind: GraphKind, improvements: bool) {
+    /// Lays `nodes` out with the Fruchterman-Reingold force-directed algorithm:
+    /// every pair of nodes repels along the vector separating them, every edge
+    /// attracts its endpoints, and the resulting displacement is capped by a
+    /// "temperature" that cools linearly to zero over `LAYOUT_ITERATIONS`.
+    fn layout(&self, nodes: &mut [Vec2], width: f32, height: f32) {
+        if self.nodes_n == 0 {
+            return;
+        }
+
+        let k = ((width * height) / self.nodes_n as f32).sqrt();
+
+        for iteration in 0..LAYOUT_ITERATIONS {
+            let mut displacements = vec![Vec2::ZERO; self.nodes_n];
+
+            for i in 0..self.nodes_n {
+                for j in 0..self.nodes_n {
+                    if i == j {
+                        continue;
+                    }
+
+                    let delta = nodes[i] - nodes[j];
+                    let d = delta.length().max(0.01);
+
+                    displacements[i] += (delta / d) * (k * k / d);
+                }
+            }
+
+            for i in 0..self.nodes_n {
+                for j in i + 1..self.nodes_n {
+                    if !self.edges[i][j] {
+                        continue;
+                    }
+
+                    let delta = nodes[i] - nodes[j];
+                    let d = delta.length().max(0.01);
+                    let attraction = (delta / d) * (d * d / k);
+
+                    displacements[i] -= attraction;
+                    displacements[j] += attraction;
+                }
+            }
+
+            let temperature =
+                width.min(height) / 10.0 * (1.0 - iteration as f32 / LAYOUT_ITERATIONS as f32);
+
+            for i in 0..self.nodes_n {
+                let displacement = displacements[i];
+                let length = displacement.length().max(0.01);
+                let step = displacement / length * length.min(temperature);
+
+                nodes[i] = (nodes[i] + step).clamp(
+                    vec2(NODE_RADIUS, NODE_RADIUS),
+                    vec2(width - NODE_RADIUS, height - NODE_RADIUS),
+                );
+            }
+        }
+    }
+
+    fn generate_with_given_kind(
+        &mut self,
+        kind: GraphKind,
+        improvements: bool,
+        edges_threshold: usize,
+        rng: &mut StdRng,
+    ) {
         loop {
-            self.generate();
+            self.generate(rng);
 
             if improvements {
                 match kind {
@@ -111,13 +270,13 @@ impl Graph {
                             .map(|x| *x as usize)
                             .sum::<usize>()
                             / 2
-                            > EDGES_THRESHOLD
+                            > edges_threshold
                         {
                             continue;
                         }
                     }
                     GraphKind::NonHamiltonian => {
-                        if (0..NODES_N)
+                        if (0..self.nodes_n)
                             .any(|i| self.edges[i].iter().map(|x| *x as usize).sum::<usize>() < 2)
                         {
                             continue;
@@ -129,44 +288,101 @@ impl Graph {
             if self.get_cycle().is_empty() != (kind == GraphKind::Hamiltonian) {
                 break;
             } else {
-                *self = Graph::new(NODES_N);
+                *self = Graph::new(self.nodes_n);
             }
         }
     }
 }
 
-enum Mode {
-    Demonstration,
-    Generation,
+/// Loads a dataset written by `Mode::Generation`, reading whichever
+/// on-disk representation `format` names so `Mode::Train` can consume a
+/// dataset exported as DOT or GraphML just as well as JSON.
+fn load_dataset(name: &str, format: Format) -> Vec<Vec<Vec<bool>>> {
+    match format {
+        Format::Json => {
+            let contents = std::fs::read_to_string(format!("{name}.json")).unwrap();
+            let content: Content = serde_json::from_str(&contents).unwrap();
+
+            content.graphs
+        }
+        Format::Dot | Format::GraphMl => {
+            let mut paths: Vec<_> = std::fs::read_dir(name)
+                .unwrap()
+                .map(|entry| entry.unwrap().path())
+                .collect();
+            paths.sort();
+
+            paths
+                .into_iter()
+                .map(|path| {
+                    let path = path.to_str().unwrap();
+
+                    let graph = match format {
+                        Format::Dot => io::read_dot(path).unwrap(),
+                        Format::GraphMl => io::read_graphml(path).unwrap(),
+                        Format::Json => unreachable!(),
+                    };
+
+                    graph.edges
+                })
+                .collect()
+        }
+    }
+}
+
+/// Keeps `nodes` in sync with `graph.nodes_n` after an edit and re-settles
+/// the layout. Doesn't try to preserve which position belonged to which
+/// node across an add/delete -- the force-directed layout reshuffles
+/// everything anyway.
+fn resync_nodes(graph: &Graph, nodes: &mut Vec<Vec2>, width: f32, height: f32) {
+    nodes.resize(graph.nodes_n, vec2(width / 2.0, height / 2.0));
+    graph.layout(nodes, width, height);
 }
-const MODE: Mode = Mode::Generation;
 
 #[macroquad::main("BasicShapes")]
 async fn main() {
-    match MODE {
+    let settings = Settings::load();
+    let nodes_n = settings.nodes_n;
+    let seed = settings.resolved_seed();
+
+    // Fails fast on an out-of-range `nodes_n` before any mode does real work.
+    Graph::new(nodes_n).safety();
+
+    match settings.mode {
         Mode::Demonstration => {
             set_fullscreen(true);
             next_frame().await;
 
-            let mut nodes = Vec::with_capacity(NODES_N);
-            let gap = (2.0 * PI) / NODES_N as f32;
+            let mut rng = StdRng::seed_from_u64(seed);
+
+            let mut nodes = Vec::with_capacity(nodes_n);
+            let gap = (2.0 * PI) / nodes_n as f32;
 
-            for i in 0..NODES_N {
+            for i in 0..nodes_n {
                 let x = GRAPH_RADIUS * (i as f32 * gap).cos() + screen_width() / 2.0;
                 let y = GRAPH_RADIUS * (i as f32 * gap).sin() + screen_height() / 2.0;
                 nodes.push(vec2(x, y))
             }
 
-            let mut graph = Graph::new(NODES_N);
+            let mut graph = Graph::new(nodes_n);
             graph.safety();
 
             let mut kind = GraphKind::Hamiltonian;
 
             let mut cycle = graph.get_cycle();
+            graph.layout(&mut nodes, screen_width(), screen_height());
+
+            let mut history = editor::History::default();
+            let mut selected: Option<usize> = None;
 
             loop {
                 if is_key_pressed(KeyCode::R) {
-                    graph.generate_with_given_kind(kind, true);
+                    graph.generate_with_given_kind(
+                        kind,
+                        settings.improvements,
+                        settings.edges_threshold,
+                        &mut rng,
+                    );
 
                     kind = match kind {
                         GraphKind::Hamiltonian => GraphKind::NonHamiltonian,
@@ -174,14 +390,67 @@ async fn main() {
                     };
 
                     cycle = graph.get_cycle();
+                    graph.layout(&mut nodes, screen_width(), screen_height());
+                }
+
+                let (mouse_x, mouse_y) = mouse_position();
+                let clicked = nodes
+                    .iter()
+                    .position(|pos| pos.distance(vec2(mouse_x, mouse_y)) <= NODE_RADIUS * 2.0);
+
+                if is_mouse_button_pressed(MouseButton::Left) {
+                    match (clicked, selected) {
+                        (Some(index), Some(from)) if index != from => {
+                            history.apply(
+                                &mut graph,
+                                Box::new(editor::ToggleEdge { i: from, j: index }),
+                            );
+                            selected = None;
+                            cycle = graph.get_cycle();
+                        }
+                        (Some(index), _) => selected = Some(index),
+                        (None, _) if graph.nodes_n < MAX_NODES_N => {
+                            history.apply(&mut graph, Box::new(editor::AddNode));
+                            resync_nodes(&graph, &mut nodes, screen_width(), screen_height());
+                            selected = None;
+                            cycle = graph.get_cycle();
+                        }
+                        (None, _) => {}
+                    }
+                } else if is_mouse_button_pressed(MouseButton::Right) {
+                    // Held-Karp needs at least 3 nodes (see `Graph::get_cycle`),
+                    // so don't let the editor delete below that floor.
+                    if let Some(index) = clicked {
+                        if graph.nodes_n > 3 {
+                            let cmd = Box::new(editor::DeleteNode::new(&graph, index));
+                            history.apply(&mut graph, cmd);
+                            resync_nodes(&graph, &mut nodes, screen_width(), screen_height());
+                            selected = None;
+                            cycle = graph.get_cycle();
+                        }
+                    }
+                }
+
+                let ctrl = is_key_down(KeyCode::LeftControl) || is_key_down(KeyCode::RightControl);
+                let shift = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+
+                if ctrl && is_key_pressed(KeyCode::Z) {
+                    if shift {
+                        history.redo(&mut graph);
+                    } else {
+                        history.undo(&mut graph);
+                    }
+
+                    resync_nodes(&graph, &mut nodes, screen_width(), screen_height());
+                    cycle = graph.get_cycle();
                 }
 
                 for node_pos in &nodes {
                     draw_circle(node_pos.x, node_pos.y, NODE_RADIUS, WHITE);
                 }
 
-                for i in 0..NODES_N {
-                    for j in i + 1..NODES_N {
+                for i in 0..graph.nodes_n {
+                    for j in i + 1..graph.nodes_n {
                         if graph.edges[i][j] {
                             draw_line(nodes[i].x, nodes[i].y, nodes[j].x, nodes[j].y, 5.0, WHITE);
                         }
@@ -189,7 +458,7 @@ async fn main() {
                 }
 
                 if !cycle.is_empty() && is_key_down(KeyCode::E) {
-                    for i in 0..NODES_N {
+                    for i in 0..graph.nodes_n {
                         let node = cycle[i];
                         let next_node = cycle[i + 1];
 
@@ -214,15 +483,30 @@ async fn main() {
             let start = Instant::now();
 
             for kind in GraphKind::ALL {
-                for _ in 0..GRAPHS_N {
-                    let mut graph = Graph::new(NODES_N);
-                    graph.safety();
-                    graph.generate_with_given_kind(kind, true);
-
-                    match kind {
-                        GraphKind::Hamiltonian => hamiltonian_graphs.push(graph.edges),
-                        GraphKind::NonHamiltonian => non_hamiltonian_graphs.push(graph.edges),
-                    }
+                // Each worker seeds its own RNG off the run's base seed so the
+                // dataset stays reproducible bit-for-bit regardless of how
+                // rayon schedules the independent generation tasks.
+                let graphs: Vec<Vec<Vec<bool>>> = (0..settings.graphs_n)
+                    .into_par_iter()
+                    .map(|i| {
+                        let mut rng = StdRng::seed_from_u64(seed.wrapping_add(i as u64));
+
+                        let mut graph = Graph::new(nodes_n);
+                        graph.safety();
+                        graph.generate_with_given_kind(
+                            kind,
+                            settings.improvements,
+                            settings.edges_threshold,
+                            &mut rng,
+                        );
+
+                        graph.edges
+                    })
+                    .collect();
+
+                match kind {
+                    GraphKind::Hamiltonian => hamiltonian_graphs = graphs,
+                    GraphKind::NonHamiltonian => non_hamiltonian_graphs = graphs,
                 }
             }
 
@@ -232,20 +516,75 @@ async fn main() {
                 (hamiltonian_graphs, "hamiltonian_graphs"),
                 (non_hamiltonian_graphs, "non_hamiltonian_graphs"),
             ] {
-                let content = Content {
-                    info: Info {
-                        nodes_n: NODES_N,
-                        graphs_n: GRAPHS_N,
-                    },
-                    graphs: graphs.clone(),
-                };
-
-                std::fs::write(
-                    format!("{}.json", name),
-                    serde_json::to_string_pretty(&content).unwrap(),
-                )
-                .unwrap()
+                match settings.format {
+                    Format::Json => {
+                        let content = Content {
+                            info: Info {
+                                nodes_n,
+                                graphs_n: settings.graphs_n,
+                                seed,
+                            },
+                            graphs: graphs.clone(),
+                        };
+
+                        std::fs::write(
+                            format!("{}.json", name),
+                            serde_json::to_string_pretty(&content).unwrap(),
+                        )
+                        .unwrap()
+                    }
+                    Format::Dot | Format::GraphMl => {
+                        std::fs::create_dir_all(name).unwrap();
+
+                        for (i, edges) in graphs.iter().enumerate() {
+                            let graph = Graph {
+                                nodes_n,
+                                edges: edges.clone(),
+                            };
+
+                            match settings.format {
+                                Format::Dot => {
+                                    io::write_dot(&graph, &format!("{name}/{i}.dot")).unwrap()
+                                }
+                                Format::GraphMl => {
+                                    io::write_graphml(&graph, &format!("{name}/{i}.graphml"))
+                                        .unwrap()
+                                }
+                                Format::Json => unreachable!(),
+                            }
+                        }
+                    }
+                }
             }
         }
+        Mode::Train => {
+            let flatten = |edges: &[Vec<bool>]| -> Vec<f32> {
+                (0..edges.len())
+                    .flat_map(|i| (i + 1..edges.len()).map(move |j| edges[i][j] as u8 as f32))
+                    .collect()
+            };
+
+            let mut inputs = Vec::new();
+            let mut labels = Vec::new();
+
+            for (name, label) in [
+                ("hamiltonian_graphs", true),
+                ("non_hamiltonian_graphs", false),
+            ] {
+                for edges in load_dataset(name, settings.format) {
+                    inputs.push(flatten(&edges));
+                    labels.push(label);
+                }
+            }
+
+            let mut rng = StdRng::seed_from_u64(seed);
+            let network = train::train(&inputs, &labels, &settings, &mut rng);
+
+            std::fs::write(
+                "model.json",
+                serde_json::to_string_pretty(&network).unwrap(),
+            )
+            .unwrap();
+        }
     }
 }
@@ -0,0 +1,86 @@
+use serde::Deserialize;
+use std::{
+    path::Path,
+    process::exit,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+#[derive(Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Mode {
+    Demonstration,
+    Generation,
+    Train,
+}
+
+/// Which on-disk representation a generated dataset is written as.
+#[derive(Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    Json,
+    Dot,
+    GraphMl,
+}
+
+/// Everything that used to be a compile-time constant, now read from a
+/// JSON/TOML file passed on the command line so a dataset can be regenerated
+/// without recompiling.
+#[derive(Deserialize)]
+pub struct Settings {
+    pub nodes_n: usize,
+    pub graphs_n: usize,
+    pub edges_threshold: usize,
+    pub improvements: bool,
+    pub mode: Mode,
+    pub seed: Option<u64>,
+    pub format: Format,
+
+    /// Size of the hidden layer of the Hamiltonicity classifier trained in
+    /// [`Mode::Train`].
+    pub hidden_n: usize,
+    /// Number of networks per generation of the genetic optimizer.
+    pub population_n: usize,
+    /// Number of generations the genetic optimizer runs for.
+    pub generations_n: usize,
+    /// Number of competitors per tournament-selection round.
+    pub tournament_size: usize,
+    /// Standard deviation of the Gaussian noise applied to each weight on
+    /// mutation.
+    pub mutation_rate: f32,
+}
+
+impl Settings {
+    /// Reads the settings file passed as the first CLI argument, deciding
+    /// between JSON and TOML by its extension.
+    pub fn load() -> Self {
+        let Some(path) = std::env::args().nth(1) else {
+            eprintln!("Usage: graphs <path to settings.json|settings.toml>");
+            exit(1);
+        };
+
+        let contents = std::fs::read_to_string(&path).unwrap_or_else(|err| {
+            eprintln!("Couldn't read settings file {path}: {err}");
+            exit(1);
+        });
+
+        match Path::new(&path).extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => toml::from_str(&contents).map_err(|err| err.to_string()),
+            _ => serde_json::from_str(&contents).map_err(|err| err.to_string()),
+        }
+        .unwrap_or_else(|err| {
+            eprintln!("Couldn't parse settings file {path}: {err}");
+            exit(1);
+        })
+    }
+
+    /// The seed actually used for this run: the configured one, or unix-time
+    /// entropy when none was given.
+    pub fn resolved_seed(&self) -> u64 {
+        self.seed.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_nanos() as u64
+        })
+    }
+}
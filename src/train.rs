@@ -0,0 +1,204 @@
+use crate::settings::Settings;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+
+/// Samples from a normal distribution with the given standard deviation via
+/// the Box-Muller transform.
+fn gaussian(rng: &mut StdRng, std_dev: f32) -> f32 {
+    let u1: f32 = rng.gen_range(f32::EPSILON..1.0);
+    let u2: f32 = rng.gen_range(0.0..1.0);
+
+    (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos() * std_dev
+}
+
+/// A single-hidden-layer feed-forward classifier predicting P(Hamiltonian)
+/// from the flattened upper triangle of an adjacency matrix.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Network {
+    /// `hidden_n x input_n`.
+    weights_input_hidden: Vec<Vec<f32>>,
+    bias_hidden: Vec<f32>,
+    /// `hidden_n`.
+    weights_hidden_output: Vec<f32>,
+    bias_output: f32,
+}
+
+impl Network {
+    fn random(input_n: usize, hidden_n: usize, rng: &mut StdRng) -> Self {
+        let weight = |rng: &mut StdRng| rng.gen_range(-1.0..1.0);
+
+        Self {
+            weights_input_hidden: (0..hidden_n)
+                .map(|_| (0..input_n).map(|_| weight(rng)).collect())
+                .collect(),
+            bias_hidden: (0..hidden_n).map(|_| weight(rng)).collect(),
+            weights_hidden_output: (0..hidden_n).map(|_| weight(rng)).collect(),
+            bias_output: weight(rng),
+        }
+    }
+
+    /// Forward pass: ReLU hidden layer, sigmoid output giving P(Hamiltonian).
+    pub fn predict(&self, input: &[f32]) -> f32 {
+        let hidden: Vec<f32> = self
+            .weights_input_hidden
+            .iter()
+            .zip(&self.bias_hidden)
+            .map(|(weights, &bias)| {
+                let sum: f32 = weights.iter().zip(input).map(|(w, x)| w * x).sum();
+                (sum + bias).max(0.0)
+            })
+            .collect();
+
+        let sum: f32 = self
+            .weights_hidden_output
+            .iter()
+            .zip(&hidden)
+            .map(|(w, h)| w * h)
+            .sum();
+
+        1.0 / (1.0 + (-(sum + self.bias_output)).exp())
+    }
+
+    fn crossover(&self, other: &Self, rng: &mut StdRng) -> Self {
+        let pick = |a: f32, b: f32, rng: &mut StdRng| if rng.gen_bool(0.5) { a } else { b };
+
+        Self {
+            weights_input_hidden: self
+                .weights_input_hidden
+                .iter()
+                .zip(&other.weights_input_hidden)
+                .map(|(a, b)| a.iter().zip(b).map(|(&a, &b)| pick(a, b, rng)).collect())
+                .collect(),
+            bias_hidden: self
+                .bias_hidden
+                .iter()
+                .zip(&other.bias_hidden)
+                .map(|(&a, &b)| pick(a, b, rng))
+                .collect(),
+            weights_hidden_output: self
+                .weights_hidden_output
+                .iter()
+                .zip(&other.weights_hidden_output)
+                .map(|(&a, &b)| pick(a, b, rng))
+                .collect(),
+            bias_output: pick(self.bias_output, other.bias_output, rng),
+        }
+    }
+
+    fn mutate(&mut self, std_dev: f32, rng: &mut StdRng) {
+        let jitter = |w: &mut f32, rng: &mut StdRng| *w += gaussian(rng, std_dev);
+
+        for row in &mut self.weights_input_hidden {
+            for w in row {
+                jitter(w, rng);
+            }
+        }
+        for w in &mut self.bias_hidden {
+            jitter(w, rng);
+        }
+        for w in &mut self.weights_hidden_output {
+            jitter(w, rng);
+        }
+        jitter(&mut self.bias_output, rng);
+    }
+}
+
+fn accuracy(network: &Network, inputs: &[Vec<f32>], labels: &[bool]) -> f32 {
+    let correct = inputs
+        .iter()
+        .zip(labels)
+        .filter(|(input, &label)| (network.predict(input) >= 0.5) == label)
+        .count();
+
+    correct as f32 / inputs.len() as f32
+}
+
+fn tournament_select<'a>(
+    population: &'a [Network],
+    fitness: &[f32],
+    tournament_size: usize,
+    rng: &mut StdRng,
+) -> &'a Network {
+    (0..tournament_size)
+        .map(|_| rng.gen_range(0..population.len()))
+        .max_by(|&a, &b| fitness[a].total_cmp(&fitness[b]))
+        .map(|i| &population[i])
+        .unwrap()
+}
+
+/// Trains a [`Network`] with a genetic optimizer: random init, fitness is
+/// classification accuracy on a held-out split, tournament selection,
+/// per-weight crossover, Gaussian-ish mutation. Reports accuracy every
+/// generation and returns the best network found.
+pub fn train(
+    inputs: &[Vec<f32>],
+    labels: &[bool],
+    settings: &Settings,
+    rng: &mut StdRng,
+) -> Network {
+    // `inputs`/`labels` arrive sorted by class (`Mode::Train` pushes every
+    // Hamiltonian graph before every non-Hamiltonian one), so the split below
+    // has to shuffle first or the held-out slice ends up single-class.
+    let mut indices: Vec<usize> = (0..inputs.len()).collect();
+    indices.shuffle(rng);
+
+    let inputs: Vec<Vec<f32>> = indices.iter().map(|&i| inputs[i].clone()).collect();
+    let labels: Vec<bool> = indices.iter().map(|&i| labels[i]).collect();
+
+    let input_n = inputs[0].len();
+    let split = inputs.len() * 4 / 5;
+
+    let (train_inputs, test_inputs) = inputs.split_at(split);
+    let (train_labels, test_labels) = labels.split_at(split);
+
+    let mut population: Vec<Network> = (0..settings.population_n)
+        .map(|_| Network::random(input_n, settings.hidden_n, rng))
+        .collect();
+
+    for generation in 0..settings.generations_n {
+        let fitness: Vec<f32> = population
+            .iter()
+            .map(|network| accuracy(network, train_inputs, train_labels))
+            .collect();
+
+        let best = fitness
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(i, _)| i)
+            .unwrap();
+
+        println!(
+            "generation {generation}: train accuracy {:.4}, held-out accuracy {:.4}",
+            fitness[best],
+            accuracy(&population[best], test_inputs, test_labels)
+        );
+
+        population = (0..settings.population_n)
+            .map(|_| {
+                let parent_a =
+                    tournament_select(&population, &fitness, settings.tournament_size, rng);
+                let parent_b =
+                    tournament_select(&population, &fitness, settings.tournament_size, rng);
+
+                let mut child = parent_a.crossover(parent_b, rng);
+                child.mutate(settings.mutation_rate, rng);
+
+                child
+            })
+            .collect();
+    }
+
+    let fitness: Vec<f32> = population
+        .iter()
+        .map(|network| accuracy(network, train_inputs, train_labels))
+        .collect();
+
+    population
+        .into_iter()
+        .zip(fitness)
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(network, _)| network)
+        .unwrap()
+}